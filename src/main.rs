@@ -1,7 +1,12 @@
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::Json,
+    body::Body,
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Extension, Path, Request, State,
+    },
+    http::{HeaderMap, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
@@ -12,22 +17,58 @@ use pumpfun::{
     PumpFun,
 };
 use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     native_token::LAMPORTS_PER_SOL,
     pubkey::Pubkey,
-    signature::Keypair,
+    signature::{Keypair, Signature},
     signer::Signer,
 };
 use spl_token;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
 use tower_http::cors::CorsLayer;
 use tracing::{info, warn};
 
+// Pump.fun's bonding curve "graduates" to Raydium once its real SOL
+// reserves reach roughly this amount; used only to report curve progress.
+const GRADUATION_LAMPORTS: u64 = 85 * LAMPORTS_PER_SOL;
+
 #[derive(Clone)]
 pub struct AppState {
-    pub pump_client: Arc<PumpFun>,
+    // Behind a lock so the background health task can rebuild the client
+    // (new RPC connection) without needing to restart the process.
+    pub pump_client: Arc<RwLock<Arc<PumpFun>>>,
     pub vanity_service: Arc<VanityService>,
+    // Same rationale as `pump_client`: the health task needs to be able to
+    // swap this out too, since it's the connection `request_and_confirm_airdrop`
+    // and the health probe itself actually use.
+    pub rpc_client: Arc<RwLock<Arc<RpcClient>>>,
+    pub rpc_url: String,
+    pub payer: Arc<Keypair>,
+    pub cluster_name: String,
+    pub curve_subscriptions: Arc<RwLock<HashMap<String, broadcast::Sender<String>>>>,
+    pub metrics: Arc<Metrics>,
+    pub caller_allowlist: Option<Arc<HashSet<Pubkey>>>,
+    pub rate_limiter: Arc<RateLimiter>,
+    pub nonce_cache: Arc<NonceCache>,
+}
+
+impl AppState {
+    // Snapshots the current `PumpFun` client so callers can make their RPC
+    // call without holding the lock for the duration of the network request.
+    async fn pump_client(&self) -> Arc<PumpFun> {
+        self.pump_client.read().await.clone()
+    }
+
+    // Same snapshot pattern as `pump_client`, for the plain RPC client used
+    // by the airdrop flow and the health probe.
+    async fn rpc_client(&self) -> Arc<RpcClient> {
+        self.rpc_client.read().await.clone()
+    }
 }
 
 #[derive(Serialize)]
@@ -66,6 +107,11 @@ struct BuyTokenRequest {
     slippage_bps: Option<u16>,
 }
 
+#[derive(Deserialize)]
+struct AirdropRequest {
+    amount_sol: Option<f64>,
+}
+
 #[derive(Deserialize)]
 struct SellTokenRequest {
     mint: String,
@@ -92,27 +138,76 @@ pub struct VanityService {
     suffix: String,
     pool_size: usize,
     authority_keypair: Keypair, // The keypair we control
+    generated_total: Arc<std::sync::atomic::AtomicU64>,
+    pool_file: Option<std::path::PathBuf>,
 }
 
 impl VanityService {
-    pub fn new(suffix: String, pool_size: usize) -> Self {
-        let authority_keypair = Keypair::new();
+    pub fn new(
+        suffix: String,
+        pool_size: usize,
+        authority_keypair: Keypair,
+        pool_file: Option<std::path::PathBuf>,
+    ) -> Self {
+        let initial_pool = pool_file
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str::<Vec<(String, String)>>(&contents).ok())
+            .unwrap_or_default();
+        if !initial_pool.is_empty() {
+            info!(
+                "Loaded {} vanity addresses from {:?}",
+                initial_pool.len(),
+                pool_file.as_ref().unwrap()
+            );
+        }
+
         let service = Self {
-            pool: Arc::new(tokio::sync::RwLock::new(Vec::new())),
+            pool: Arc::new(tokio::sync::RwLock::new(initial_pool)),
             suffix,
             pool_size,
             authority_keypair,
+            generated_total: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            pool_file,
         };
-        
+
         // Start background generation
         let service_clone = service.clone();
         tokio::spawn(async move {
             service_clone.generate_pool().await;
         });
-        
+
+        // Periodically persist the pool so generation work survives a restart.
+        if service.pool_file.is_some() {
+            let snapshot_service = service.clone();
+            tokio::spawn(async move {
+                loop {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
+                    snapshot_service.save_snapshot().await;
+                }
+            });
+        }
+
         service
     }
-    
+
+    // Writes the current pool to `pool_file`, if configured. Called on the
+    // periodic snapshot timer and once more during graceful shutdown.
+    pub async fn save_snapshot(&self) {
+        let Some(path) = &self.pool_file else {
+            return;
+        };
+        let pool = self.pool.read().await;
+        match serde_json::to_string(&*pool) {
+            Ok(json) => {
+                if let Err(e) = tokio::fs::write(path, json).await {
+                    warn!("Failed to snapshot vanity pool to {:?}: {}", path, e);
+                }
+            }
+            Err(e) => warn!("Failed to serialize vanity pool snapshot: {}", e),
+        }
+    }
+
     pub async fn get_next_vanity(&self) -> Option<(String, String)> {
         let mut pool = self.pool.write().await;
         pool.pop()
@@ -121,7 +216,11 @@ impl VanityService {
     pub async fn pool_size(&self) -> usize {
         self.pool.read().await.len()
     }
-    
+
+    pub fn generated_total(&self) -> u64 {
+        self.generated_total.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
     async fn generate_pool(&self) {
         info!("Starting FAST vanity address generation for suffix: {}", self.suffix);
         info!("Authority pubkey: {}", self.authority_keypair.pubkey());
@@ -193,7 +292,10 @@ impl VanityService {
         
         // Extract the results from the mutex
         let mut pool = found.lock().await;
-        std::mem::take(&mut *pool)
+        let batch = std::mem::take(&mut *pool);
+        self.generated_total
+            .fetch_add(batch.len() as u64, std::sync::atomic::Ordering::Relaxed);
+        batch
     }
     
     // Get the authority keypair for creating tokens
@@ -209,8 +311,394 @@ impl Clone for VanityService {
             suffix: self.suffix.clone(),
             pool_size: self.pool_size,
             authority_keypair: self.authority_keypair.insecure_clone(),
+            generated_total: self.generated_total.clone(),
+            pool_file: self.pool_file.clone(),
+        }
+    }
+}
+
+// Fixed-bucket histogram bounds in milliseconds, cumulative like Prometheus'
+// own `_bucket{le="..."}` series (each bucket counts samples <= its bound).
+const LATENCY_BUCKETS_MS: [f64; 10] = [
+    5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0,
+];
+
+struct Histogram {
+    buckets: Vec<std::sync::atomic::AtomicU64>, // one per LATENCY_BUCKETS_MS entry, plus a trailing +Inf bucket
+    sum_micros: std::sync::atomic::AtomicU64,
+    count: std::sync::atomic::AtomicU64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            buckets: (0..=LATENCY_BUCKETS_MS.len())
+                .map(|_| std::sync::atomic::AtomicU64::new(0))
+                .collect(),
+            sum_micros: std::sync::atomic::AtomicU64::new(0),
+            count: std::sync::atomic::AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, millis: f64) {
+        use std::sync::atomic::Ordering::Relaxed;
+
+        for (i, bound) in LATENCY_BUCKETS_MS.iter().enumerate() {
+            if millis <= *bound {
+                self.buckets[i].fetch_add(1, Relaxed);
+            }
         }
+        // The last bucket is +Inf, so every sample lands in it.
+        self.buckets[LATENCY_BUCKETS_MS.len()].fetch_add(1, Relaxed);
+
+        self.sum_micros.fetch_add((millis * 1000.0) as u64, Relaxed);
+        self.count.fetch_add(1, Relaxed);
     }
+
+    fn render(&self, name: &str, out: &mut String) {
+        use std::sync::atomic::Ordering::Relaxed;
+        use std::fmt::Write;
+
+        for (i, bound) in LATENCY_BUCKETS_MS.iter().enumerate() {
+            let count = self.buckets[i].load(Relaxed);
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, count);
+        }
+        let inf_count = self.buckets[LATENCY_BUCKETS_MS.len()].load(Relaxed);
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, inf_count);
+        let sum_ms = self.sum_micros.load(Relaxed) as f64 / 1000.0;
+        let _ = writeln!(out, "{}_sum {}", name, sum_ms);
+        let _ = writeln!(out, "{}_count {}", name, self.count.load(Relaxed));
+    }
+}
+
+// Per-endpoint / per-operation latency histograms, exposed in Prometheus
+// text exposition format at `GET /metrics`.
+pub struct Metrics {
+    histograms: RwLock<HashMap<String, Arc<Histogram>>>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            histograms: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn histogram(&self, name: &str) -> Arc<Histogram> {
+        if let Some(hist) = self.histograms.read().await.get(name) {
+            return hist.clone();
+        }
+        let mut histograms = self.histograms.write().await;
+        histograms
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(Histogram::new()))
+            .clone()
+    }
+
+    pub async fn observe(&self, name: &str, millis: f64) {
+        self.histogram(name).await.observe(millis);
+    }
+
+    pub async fn render(&self) -> String {
+        let mut out = String::new();
+        for (name, hist) in self.histograms.read().await.iter() {
+            use std::fmt::Write;
+            let _ = writeln!(out, "# TYPE {} histogram", name);
+            hist.render(name, &mut out);
+        }
+        out
+    }
+}
+
+// Times `fut`, records the elapsed milliseconds under `label`, and returns
+// the future's output unchanged.
+async fn observe_duration<T>(
+    metrics: &Metrics,
+    label: &str,
+    fut: impl std::future::Future<Output = T>,
+) -> T {
+    let start = std::time::Instant::now();
+    let result = fut.await;
+    metrics
+        .observe(label, start.elapsed().as_secs_f64() * 1000.0)
+        .await;
+    result
+}
+
+// Token-bucket rate limiter keyed by caller pubkey, so one allow-listed key
+// can't drain the authority's funds or hammer the RPC.
+struct TokenBucket {
+    tokens: f64,
+    last_refill: std::time::Instant,
+}
+
+pub struct RateLimiter {
+    requests_per_sec: f64,
+    burst: f64,
+    buckets: RwLock<HashMap<Pubkey, TokenBucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_sec: f64, burst: f64) -> Self {
+        Self {
+            requests_per_sec,
+            burst,
+            buckets: RwLock::new(HashMap::new()),
+        }
+    }
+
+    // Refills `caller`'s bucket for elapsed time and consumes one token.
+    // Returns false (and leaves the bucket empty) once the caller is over
+    // its configured rate.
+    pub async fn try_acquire(&self, caller: Pubkey) -> bool {
+        let mut buckets = self.buckets.write().await;
+        let now = std::time::Instant::now();
+        let bucket = buckets.entry(caller).or_insert_with(|| TokenBucket {
+            tokens: self.burst,
+            last_refill: now,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.requests_per_sec).min(self.burst);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+// How far a caller's `X-Caller-Nonce` timestamp may drift from server time
+// before a request is rejected as a (likely replayed) stale signature.
+const MAX_NONCE_SKEW_MILLIS: u64 = 30_000;
+
+// Tracks `(pubkey, nonce)` pairs that have already been accepted, so a
+// captured valid signed request can't simply be replayed within the skew
+// window. An accepted nonce lies within `MAX_NONCE_SKEW_MILLIS` of server
+// time on either side, so the skew check rejects any replay of it at most
+// twice that long after it was recorded; entries older than
+// `2 * MAX_NONCE_SKEW_MILLIS` are therefore evicted on every insert instead
+// of by a separate sweep task.
+pub struct NonceCache {
+    seen: RwLock<HashMap<(Pubkey, u64), std::time::Instant>>,
+}
+
+impl NonceCache {
+    pub fn new() -> Self {
+        Self {
+            seen: RwLock::new(HashMap::new()),
+        }
+    }
+
+    // Returns true (and records the nonce) the first time `(caller, nonce)`
+    // is seen; returns false on any repeat, i.e. a replay.
+    pub async fn check_and_record(&self, caller: Pubkey, nonce_millis: u64) -> bool {
+        let mut seen = self.seen.write().await;
+
+        let cutoff = std::time::Instant::now()
+            .checked_sub(std::time::Duration::from_millis(MAX_NONCE_SKEW_MILLIS * 2))
+            .unwrap_or_else(std::time::Instant::now);
+        seen.retain(|_, inserted_at| *inserted_at >= cutoff);
+
+        match seen.entry((caller, nonce_millis)) {
+            std::collections::hash_map::Entry::Occupied(_) => false,
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(std::time::Instant::now());
+                true
+            }
+        }
+    }
+}
+
+// Verifies the `X-Caller-Pubkey` / `X-Caller-Nonce` / `X-Caller-Signature`
+// headers against `allowlist`, mirroring a service-transaction whitelist:
+// the caller signs `nonce || sha256(body)` with its Solana key, and the
+// server checks that signature against the claimed (and allow-listed) pubkey.
+// `nonce_cache` rejects a second use of the same (pubkey, nonce) pair so a
+// captured request can't just be replayed verbatim within the skew window.
+async fn authenticate_caller(
+    allowlist: &HashSet<Pubkey>,
+    nonce_cache: &NonceCache,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<Pubkey, &'static str> {
+    let pubkey_str = headers
+        .get("x-caller-pubkey")
+        .and_then(|v| v.to_str().ok())
+        .ok_or("missing X-Caller-Pubkey header")?;
+    let nonce_str = headers
+        .get("x-caller-nonce")
+        .and_then(|v| v.to_str().ok())
+        .ok_or("missing X-Caller-Nonce header")?;
+    let signature_str = headers
+        .get("x-caller-signature")
+        .and_then(|v| v.to_str().ok())
+        .ok_or("missing X-Caller-Signature header")?;
+
+    let pubkey: Pubkey = pubkey_str.parse().map_err(|_| "invalid caller pubkey")?;
+    if !allowlist.contains(&pubkey) {
+        return Err("caller pubkey not in allowlist");
+    }
+
+    let nonce_millis: u64 = nonce_str.parse().map_err(|_| "invalid nonce")?;
+    let now_millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    if now_millis.abs_diff(nonce_millis) > MAX_NONCE_SKEW_MILLIS {
+        return Err("stale nonce");
+    }
+
+    let signature: Signature = signature_str.parse().map_err(|_| "invalid signature encoding")?;
+
+    let body_hash = solana_sdk::hash::hash(body);
+    let mut message = Vec::with_capacity(nonce_str.len() + body_hash.as_ref().len());
+    message.extend_from_slice(nonce_str.as_bytes());
+    message.extend_from_slice(body_hash.as_ref());
+
+    if !signature.verify(pubkey.as_ref(), &message) {
+        return Err("signature verification failed");
+    }
+
+    if !nonce_cache.check_and_record(pubkey, nonce_millis).await {
+        return Err("nonce already used");
+    }
+
+    Ok(pubkey)
+}
+
+// Middleware for the mutating `/tx/*` routes: when `CALLER_ALLOWLIST` is
+// configured, requires a valid signed-caller header set and applies the
+// per-caller rate limit; otherwise passes requests through unchanged.
+async fn auth_rate_limit_middleware(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let Some(allowlist) = state.caller_allowlist.clone() else {
+        return next.run(req).await;
+    };
+
+    let (parts, body) = req.into_parts();
+    let body_bytes = match axum::body::to_bytes(body, 1024 * 1024).await {
+        Ok(bytes) => bytes,
+        Err(_) => return (StatusCode::BAD_REQUEST, "failed to read request body").into_response(),
+    };
+
+    let caller = match authenticate_caller(&allowlist, &state.nonce_cache, &parts.headers, &body_bytes).await {
+        Ok(caller) => caller,
+        Err(msg) => {
+            warn!("Rejected unauthenticated trading request: {}", msg);
+            return (StatusCode::UNAUTHORIZED, msg).into_response();
+        }
+    };
+
+    if !state.rate_limiter.try_acquire(caller).await {
+        warn!("Rate limit exceeded for caller {}", caller);
+        return (StatusCode::TOO_MANY_REQUESTS, "rate limit exceeded").into_response();
+    }
+
+    let mut parts = parts;
+    // Downstream handlers (namely rpc_handler, which can fan a single HTTP
+    // request out into a batch of JSON-RPC calls) need the authenticated
+    // caller to charge the rate limiter per call, not just per HTTP request.
+    parts.extensions.insert(caller);
+    let req = Request::from_parts(parts, Body::from(body_bytes));
+    next.run(req).await
+}
+
+fn build_cluster_config(cluster_name: &str) -> Cluster {
+    match cluster_name {
+        "mainnet" => Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default()),
+        "devnet" => Cluster::devnet(CommitmentConfig::confirmed(), PriorityFee::default()),
+        "testnet" => Cluster::testnet(CommitmentConfig::confirmed(), PriorityFee::default()),
+        _ => Cluster::devnet(CommitmentConfig::confirmed(), PriorityFee::default()),
+    }
+}
+
+// Loads the authority keypair from `path` if present, otherwise generates a
+// fresh one and writes it there, so restarts keep paying from the same
+// address instead of minting a brand new (unfunded) payer each time.
+fn load_or_create_authority_keypair(path: &str) -> Keypair {
+    if let Ok(keypair) = solana_sdk::signature::read_keypair_file(path) {
+        info!("Loaded authority keypair from {}", path);
+        return keypair;
+    }
+
+    let keypair = Keypair::new();
+    match solana_sdk::signature::write_keypair_file(&keypair, path) {
+        Ok(_) => info!("Generated new authority keypair and saved it to {}", path),
+        Err(e) => warn!("Generated new authority keypair but failed to persist it to {}: {}", path, e),
+    }
+    keypair
+}
+
+// Pings the RPC on an interval and rebuilds both `state.rpc_client` (the
+// connection this very probe, and the airdrop flow, actually use) and
+// `state.pump_client` after a run of consecutive failures, so a stale/broken
+// connection self-heals instead of wedging the process until a manual restart.
+async fn rpc_health_task(state: AppState) {
+    const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+    const FAILURE_THRESHOLD: u32 = 3;
+
+    let mut consecutive_failures = 0u32;
+    loop {
+        tokio::time::sleep(CHECK_INTERVAL).await;
+
+        let rpc_client = state.rpc_client().await;
+        match rpc_client.get_latest_blockhash().await {
+            Ok(_) => consecutive_failures = 0,
+            Err(e) => {
+                consecutive_failures += 1;
+                warn!(
+                    "RPC health check failed ({}/{}): {}",
+                    consecutive_failures, FAILURE_THRESHOLD, e
+                );
+
+                if consecutive_failures >= FAILURE_THRESHOLD {
+                    info!("Rebuilding RPC and PumpFun clients after repeated RPC failures");
+                    let new_rpc_client = Arc::new(RpcClient::new_with_commitment(
+                        state.rpc_url.clone(),
+                        CommitmentConfig::confirmed(),
+                    ));
+                    *state.rpc_client.write().await = new_rpc_client;
+
+                    let cluster_config = build_cluster_config(&state.cluster_name);
+                    let new_pump_client = Arc::new(PumpFun::new(state.payer.clone(), cluster_config));
+                    *state.pump_client.write().await = new_pump_client;
+
+                    consecutive_failures = 0;
+                }
+            }
+        }
+    }
+}
+
+// Waits for SIGINT/SIGTERM, then flushes the vanity-pool snapshot so the
+// next startup doesn't lose in-progress generation work.
+async fn shutdown_signal(state: AppState) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+
+    info!("Shutdown signal received, flushing vanity pool snapshot...");
+    state.vanity_service.save_snapshot().await;
 }
 
 #[tokio::main]
@@ -227,79 +715,241 @@ async fn main() {
     
     // Load configuration
     let cluster = std::env::var("SOLANA_CLUSTER").unwrap_or_else(|_| "devnet".to_string());
-    let _rpc_url = std::env::var("RPC_URL").unwrap_or_else(|_| "https://api.devnet.solana.com".to_string());
+    let rpc_url = std::env::var("RPC_URL").unwrap_or_else(|_| "https://api.devnet.solana.com".to_string());
     let vanity_suffix = std::env::var("VANITY_SUFFIX").unwrap_or_else(|_| "pump".to_string());
     let vanity_pool_size = std::env::var("VANITY_POOL_SIZE")
         .unwrap_or_else(|_| "120".to_string())
         .parse()
         .unwrap_or(120);
-    
-    // Create vanity service first
-    let vanity_service = Arc::new(VanityService::new(vanity_suffix, vanity_pool_size));
-    
-    // Use the authority keypair from vanity service as payer
-    let payer = Arc::new(vanity_service.get_authority_keypair().insecure_clone());
+    let authority_keyfile = std::env::var("AUTHORITY_KEYFILE")
+        .unwrap_or_else(|_| "authority-keypair.json".to_string());
+    let vanity_pool_file = std::env::var("VANITY_POOL_FILE")
+        .unwrap_or_else(|_| "vanity-pool.json".to_string());
+
+    // Load (or generate and persist) the authority keypair so restarts keep
+    // paying from the same address instead of minting a fresh, unfunded one.
+    let authority_keypair = load_or_create_authority_keypair(&authority_keyfile);
+    let payer = Arc::new(authority_keypair.insecure_clone());
     info!("Authority/Payer public key: {}", payer.pubkey());
-    
+
+    // Create vanity service, reloading any pool snapshot left by a prior run.
+    let vanity_service = Arc::new(VanityService::new(
+        vanity_suffix,
+        vanity_pool_size,
+        authority_keypair,
+        Some(std::path::PathBuf::from(vanity_pool_file)),
+    ));
+
     // Create PumpFun client
-    let cluster_config = match cluster.as_str() {
-        "mainnet" => Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default()),
-        "devnet" => Cluster::devnet(CommitmentConfig::confirmed(), PriorityFee::default()),
-        "testnet" => Cluster::testnet(CommitmentConfig::confirmed(), PriorityFee::default()),
-        _ => Cluster::devnet(CommitmentConfig::confirmed(), PriorityFee::default()),
-    };
-    
-    let pump_client = Arc::new(PumpFun::new(payer, cluster_config));
+    let cluster_config = build_cluster_config(&cluster);
+    let pump_client = Arc::new(RwLock::new(Arc::new(PumpFun::new(payer.clone(), cluster_config))));
     info!("PumpFun client initialized for cluster: {}", cluster);
-    
+
+    let rpc_client = Arc::new(RwLock::new(Arc::new(RpcClient::new_with_commitment(
+        rpc_url.clone(),
+        CommitmentConfig::confirmed(),
+    ))));
+
+    // Optional pubkey allow-list for the mutating /tx/* routes. Unset means
+    // those routes stay open, matching today's behavior.
+    let caller_allowlist = std::env::var("CALLER_ALLOWLIST").ok().map(|raw| {
+        let pubkeys: HashSet<Pubkey> = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .filter_map(|s| match s.parse() {
+                Ok(pubkey) => Some(pubkey),
+                Err(_) => {
+                    warn!("Ignoring invalid CALLER_ALLOWLIST entry: {}", s);
+                    None
+                }
+            })
+            .collect();
+        Arc::new(pubkeys)
+    });
+    if caller_allowlist.is_some() {
+        info!("Caller allow-list enabled for /tx/* routes");
+    }
+
+    let rate_limit_rps = std::env::var("RATE_LIMIT_RPS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(5.0);
+    let rate_limit_burst = std::env::var("RATE_LIMIT_BURST")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(10.0);
+
     // Create app state
     let state = AppState {
         pump_client,
         vanity_service,
+        rpc_client,
+        rpc_url: rpc_url.clone(),
+        payer: payer.clone(),
+        cluster_name: cluster.clone(),
+        curve_subscriptions: Arc::new(RwLock::new(HashMap::new())),
+        metrics: Arc::new(Metrics::new()),
+        caller_allowlist,
+        rate_limiter: Arc::new(RateLimiter::new(rate_limit_rps, rate_limit_burst)),
+        nonce_cache: Arc::new(NonceCache::new()),
     };
-    
-    // Build router
-    let app = Router::new()
-        .route("/health", get(health_handler))
+
+    // Bootstrap-fund the authority keypair on non-mainnet clusters so the
+    // service is usable without a manual `solana airdrop` beforehand.
+    if cluster != "mainnet" {
+        let bootstrap_state = state.clone();
+        tokio::spawn(async move {
+            match request_and_confirm_airdrop(&bootstrap_state, 2.0).await {
+                Ok(signature) => info!("Bootstrap airdrop confirmed: {}", signature),
+                Err(e) => warn!("Bootstrap airdrop failed (continuing anyway): {}", e),
+            }
+        });
+    }
+
+    // Trading routes carry the auth/rate-limit layer; read-only routes don't.
+    // /rpc and /airdrop are included here too: /rpc dispatches to the same
+    // mutating do_create_token/do_buy/do_sell/do_create_and_buy logic as the
+    // REST routes above, and /airdrop both spends the authority keypair's SOL
+    // (on devnet/testnet faucets) and ties up a task for the duration of its
+    // confirm-polling loop, so neither may be reachable unauthenticated.
+    let trading_routes = Router::new()
         .route("/tx/create", post(create_token_handler))
         .route("/tx/create-and-buy", post(create_and_buy_handler))
         .route("/tx/buy", post(buy_token_handler))
         .route("/tx/sell", post(sell_token_handler))
+        .route("/rpc", post(rpc_handler))
+        .route("/airdrop", post(airdrop_handler))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth_rate_limit_middleware,
+        ));
+
+    // Watch the RPC endpoint in the background and swap in a fresh PumpFun
+    // client if it looks unhealthy for several checks in a row.
+    tokio::spawn(rpc_health_task(state.clone()));
+
+    // Build router
+    let app = Router::new()
+        .route("/health", get(health_handler))
+        .merge(trading_routes)
         .route("/token/:mint/curve", get(get_curve_handler))
+        .route("/token/:mint/subscribe", get(subscribe_curve_handler))
         .route("/vanity/stats", get(vanity_stats_handler))
+        .route("/metrics", get(metrics_handler))
         .layer(CorsLayer::permissive())
-        .with_state(state);
-    
+        .with_state(state.clone());
+
     // Start server
     let host = std::env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
     let port = std::env::var("PORT").unwrap_or_else(|_| "3001".to_string()).parse().unwrap_or(3001);
-    
+
     let listener = tokio::net::TcpListener::bind(format!("{}:{}", host, port))
         .await
         .expect("Failed to bind to address");
-    
+
     info!("Server running on {}:{}", host, port);
-    axum::serve(listener, app).await.expect("Server failed to start");
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal(state))
+        .await
+        .expect("Server failed to start");
 }
 
-async fn health_handler(State(state): State<AppState>) -> Result<Json<HealthResponse>, StatusCode> {
+// Business-logic error shared between the REST handlers and the JSON-RPC
+// dispatcher, so both can report the same failure in their own wire format
+// (an HTTP status for REST, a machine-distinguishable code/message for RPC).
+#[derive(Debug)]
+enum ApiError {
+    BadRequest(String),
+    Internal(String),
+    MethodNotFound(String),
+}
+
+impl ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::MethodNotFound(_) => StatusCode::NOT_FOUND,
+        }
+    }
+}
+
+async fn do_health(state: &AppState) -> HealthResponse {
     let vanity_pool_size = state.vanity_service.pool_size().await;
-    
-    Ok(Json(HealthResponse {
+
+    HealthResponse {
         status: "healthy".to_string(),
-        cluster: "devnet".to_string(),
+        cluster: state.cluster_name.clone(),
         vanity_pool_size,
-    }))
+    }
 }
 
-async fn create_token_handler(
+async fn health_handler(State(state): State<AppState>) -> Result<Json<HealthResponse>, StatusCode> {
+    Ok(Json(do_health(&state).await))
+}
+
+async fn airdrop_handler(
     State(state): State<AppState>,
-    Json(request): Json<CreateTokenRequest>,
+    Json(request): Json<AirdropRequest>,
 ) -> Result<Json<TransactionResponse>, StatusCode> {
+    if state.cluster_name == "mainnet" {
+        warn!("Refusing airdrop request on mainnet");
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let amount_sol = request.amount_sol.unwrap_or(1.0);
+
+    match request_and_confirm_airdrop(&state, amount_sol).await {
+        Ok(signature) => Ok(Json(TransactionResponse {
+            signature: signature.to_string(),
+            mint: None,
+        })),
+        Err(e) => {
+            warn!("Airdrop failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+// Requests an airdrop for `state.pump_client`'s payer and polls until the
+// RPC reports the transaction confirmed, mirroring the drone/airdrop flow
+// the Solana CLI uses against devnet/testnet faucets.
+async fn request_and_confirm_airdrop(
+    state: &AppState,
+    amount_sol: f64,
+) -> Result<Signature, Box<dyn std::error::Error + Send + Sync>> {
+    let payer_pubkey = state.payer.pubkey();
+    let lamports = (amount_sol * LAMPORTS_PER_SOL as f64) as u64;
+
+    info!("Requesting airdrop of {} SOL for {}", amount_sol, payer_pubkey);
+    let rpc_client = state.rpc_client().await;
+    let signature = rpc_client.request_airdrop(&payer_pubkey, lamports).await?;
+
+    for _ in 0..30 {
+        if rpc_client
+            .confirm_transaction_with_commitment(&signature, CommitmentConfig::confirmed())
+            .await
+            .map(|res| res.value)
+            .unwrap_or(false)
+        {
+            return Ok(signature);
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+
+    Err(format!("airdrop {} not confirmed in time", signature).into())
+}
+
+async fn do_create_token(
+    state: &AppState,
+    request: CreateTokenRequest,
+) -> Result<TransactionResponse, ApiError> {
+    let handler_start = std::time::Instant::now();
     info!("Creating token: {} ({})", request.name, request.symbol);
-    
+
     // Get vanity seed and pubkey
-    let (seed, vanity_pubkey) = if request.use_vanity.unwrap_or(true) {
+    let (_seed, vanity_pubkey) = if request.use_vanity.unwrap_or(true) {
         state.vanity_service.get_next_vanity().await
             .unwrap_or_else(|| {
                 warn!("No vanity keypairs available, using random keypair");
@@ -310,10 +960,10 @@ async fn create_token_handler(
         let random_keypair = Keypair::new();
         ("random".to_string(), random_keypair.pubkey().to_string())
     };
-    
+
     let mint_pubkey = vanity_pubkey.parse::<Pubkey>()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    
+        .map_err(|_| ApiError::Internal("failed to parse generated vanity pubkey".to_string()))?;
+
     // Create metadata
     let metadata = CreateTokenMetadata {
         name: request.name,
@@ -324,31 +974,57 @@ async fn create_token_handler(
         telegram: request.telegram,
         website: request.website,
     };
-    
+
     // Create token using the vanity pubkey
-    match state.pump_client.create(mint_pubkey, metadata, None).await {
+    let create_result = observe_duration(
+        &state.metrics,
+        "pump_client_create_duration_ms",
+        state.pump_client().await.create(mint_pubkey, metadata, None),
+    )
+    .await;
+
+    let response = match create_result {
         Ok(signature) => {
             info!("Token created successfully: {} with vanity address: {}", signature, vanity_pubkey);
-            Ok(Json(TransactionResponse {
+            Ok(TransactionResponse {
                 signature: signature.to_string(),
                 mint: Some(vanity_pubkey),
-            }))
+            })
         }
         Err(e) => {
             warn!("Failed to create token: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(ApiError::Internal(e.to_string()))
         }
-    }
+    };
+    state
+        .metrics
+        .observe(
+            "create_token_handler_duration_ms",
+            handler_start.elapsed().as_secs_f64() * 1000.0,
+        )
+        .await;
+    response
 }
 
-async fn create_and_buy_handler(
+async fn create_token_handler(
     State(state): State<AppState>,
-    Json(request): Json<CreateAndBuyRequest>,
+    Json(request): Json<CreateTokenRequest>,
 ) -> Result<Json<TransactionResponse>, StatusCode> {
+    do_create_token(&state, request)
+        .await
+        .map(Json)
+        .map_err(|e| e.status_code())
+}
+
+async fn do_create_and_buy(
+    state: &AppState,
+    request: CreateAndBuyRequest,
+) -> Result<TransactionResponse, ApiError> {
+    let handler_start = std::time::Instant::now();
     info!("Creating and buying token: {} ({})", request.create.name, request.create.symbol);
-    
+
     // Get vanity seed and pubkey
-    let (seed, vanity_pubkey) = if request.create.use_vanity.unwrap_or(true) {
+    let (_seed, vanity_pubkey) = if request.create.use_vanity.unwrap_or(true) {
         state.vanity_service.get_next_vanity().await
             .unwrap_or_else(|| {
                 warn!("No vanity keypairs available, using random keypair");
@@ -359,10 +1035,10 @@ async fn create_and_buy_handler(
         let random_keypair = Keypair::new();
         ("random".to_string(), random_keypair.pubkey().to_string())
     };
-    
+
     let mint_pubkey = vanity_pubkey.parse::<Pubkey>()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    
+        .map_err(|_| ApiError::Internal("failed to parse generated vanity pubkey".to_string()))?;
+
     // Create metadata
     let metadata = CreateTokenMetadata {
         name: request.create.name,
@@ -373,134 +1049,694 @@ async fn create_and_buy_handler(
         telegram: request.create.telegram,
         website: request.create.website,
     };
-    
+
     // Convert SOL to lamports
     let lamports = (request.amount_sol * LAMPORTS_PER_SOL as f64) as u64;
-    
+
     // Create and buy token using the vanity pubkey
-    match state.pump_client.create_and_buy(
-        mint_pubkey,
-        metadata,
-        lamports,
-        request.create.track_volume,
-        None, // slippage
-        None, // priority fee
-    ).await {
+    let create_and_buy_result = observe_duration(
+        &state.metrics,
+        "pump_client_create_and_buy_duration_ms",
+        state.pump_client().await.create_and_buy(
+            mint_pubkey,
+            metadata,
+            lamports,
+            request.create.track_volume,
+            None, // slippage
+            None, // priority fee
+        ),
+    )
+    .await;
+
+    let response = match create_and_buy_result {
         Ok(signature) => {
             info!("Token created and bought successfully: {} with vanity address: {}", signature, vanity_pubkey);
-            Ok(Json(TransactionResponse {
+            Ok(TransactionResponse {
                 signature: signature.to_string(),
                 mint: Some(vanity_pubkey),
-            }))
+            })
         }
         Err(e) => {
             warn!("Failed to create and buy token: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(ApiError::Internal(e.to_string()))
         }
-    }
+    };
+    state
+        .metrics
+        .observe(
+            "create_and_buy_handler_duration_ms",
+            handler_start.elapsed().as_secs_f64() * 1000.0,
+        )
+        .await;
+    response
 }
 
-async fn buy_token_handler(
+async fn create_and_buy_handler(
     State(state): State<AppState>,
-    Json(request): Json<BuyTokenRequest>,
+    Json(request): Json<CreateAndBuyRequest>,
 ) -> Result<Json<TransactionResponse>, StatusCode> {
+    do_create_and_buy(&state, request)
+        .await
+        .map(Json)
+        .map_err(|e| e.status_code())
+}
+
+async fn do_buy(state: &AppState, request: BuyTokenRequest) -> Result<TransactionResponse, ApiError> {
+    let handler_start = std::time::Instant::now();
     info!("Buying token: {}", request.mint);
-    
-    let mint_pubkey = request.mint.parse()
-        .map_err(|_| StatusCode::BAD_REQUEST)?;
-    
+
+    let mint_pubkey: Pubkey = request.mint.parse()
+        .map_err(|_| ApiError::BadRequest(format!("invalid mint pubkey: {}", request.mint)))?;
+
     let lamports = (request.amount_sol * LAMPORTS_PER_SOL as f64) as u64;
-    
-    match state.pump_client.buy(
-        mint_pubkey,
-        lamports,
-        request.track_volume,
-        None, // slippage
-        None, // priority fee
-    ).await {
+
+    let buy_result = observe_duration(
+        &state.metrics,
+        "pump_client_buy_duration_ms",
+        state.pump_client().await.buy(
+            mint_pubkey,
+            lamports,
+            request.track_volume,
+            None, // slippage
+            None, // priority fee
+        ),
+    )
+    .await;
+
+    let response = match buy_result {
         Ok(signature) => {
             info!("Token bought successfully: {}", signature);
-            Ok(Json(TransactionResponse {
+            Ok(TransactionResponse {
                 signature: signature.to_string(),
                 mint: None,
-            }))
+            })
         }
         Err(e) => {
             warn!("Failed to buy token: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(ApiError::Internal(e.to_string()))
         }
-    }
+    };
+    state
+        .metrics
+        .observe(
+            "buy_token_handler_duration_ms",
+            handler_start.elapsed().as_secs_f64() * 1000.0,
+        )
+        .await;
+    response
 }
 
-async fn sell_token_handler(
+async fn buy_token_handler(
     State(state): State<AppState>,
-    Json(request): Json<SellTokenRequest>,
+    Json(request): Json<BuyTokenRequest>,
 ) -> Result<Json<TransactionResponse>, StatusCode> {
+    do_buy(&state, request).await.map(Json).map_err(|e| e.status_code())
+}
+
+async fn do_sell(state: &AppState, request: SellTokenRequest) -> Result<TransactionResponse, ApiError> {
+    let handler_start = std::time::Instant::now();
     info!("Selling token: {}", request.mint);
-    
-    let mint_pubkey = request.mint.parse()
-        .map_err(|_| StatusCode::BAD_REQUEST)?;
-    
+
+    let mint_pubkey: Pubkey = request.mint.parse()
+        .map_err(|_| ApiError::BadRequest(format!("invalid mint pubkey: {}", request.mint)))?;
+
     let amount = if request.sell_all.unwrap_or(false) {
         None
     } else {
         request.amount_tokens
     };
-    
-    match state.pump_client.sell(
-        mint_pubkey,
-        amount,
-        None, // slippage
-        None, // priority fee
-    ).await {
+
+    let sell_result = observe_duration(
+        &state.metrics,
+        "pump_client_sell_duration_ms",
+        state.pump_client().await.sell(
+            mint_pubkey,
+            amount,
+            None, // slippage
+            None, // priority fee
+        ),
+    )
+    .await;
+
+    let response = match sell_result {
         Ok(signature) => {
             info!("Token sold successfully: {}", signature);
-            Ok(Json(TransactionResponse {
+            Ok(TransactionResponse {
                 signature: signature.to_string(),
                 mint: None,
-            }))
+            })
         }
         Err(e) => {
             warn!("Failed to sell token: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(ApiError::Internal(e.to_string()))
         }
-    }
+    };
+    state
+        .metrics
+        .observe(
+            "sell_token_handler_duration_ms",
+            handler_start.elapsed().as_secs_f64() * 1000.0,
+        )
+        .await;
+    response
 }
 
-async fn get_curve_handler(
+async fn sell_token_handler(
     State(state): State<AppState>,
-    axum::extract::Path(mint): axum::extract::Path<String>,
-) -> Result<Json<CurveResponse>, StatusCode> {
-    let mint_pubkey = mint.parse()
-        .map_err(|_| StatusCode::BAD_REQUEST)?;
-    
-    match state.pump_client.get_bonding_curve_account(&mint_pubkey).await {
+    Json(request): Json<SellTokenRequest>,
+) -> Result<Json<TransactionResponse>, StatusCode> {
+    do_sell(&state, request).await.map(Json).map_err(|e| e.status_code())
+}
+
+async fn do_get_curve(state: &AppState, mint: String) -> Result<CurveResponse, ApiError> {
+    let mint_pubkey: Pubkey = mint.parse()
+        .map_err(|_| ApiError::BadRequest(format!("invalid mint pubkey: {}", mint)))?;
+
+    match state.pump_client().await.get_bonding_curve_account(&mint_pubkey).await {
         Ok(curve) => {
             // Convert BondingCurveAccount to JSON
             let curve_json = serde_json::to_value(&curve)
                 .unwrap_or_else(|_| serde_json::json!({"error": "Failed to serialize curve"}));
-            
-            Ok(Json(CurveResponse {
+
+            Ok(CurveResponse {
                 mint,
                 curve: curve_json,
-            }))
+            })
         }
         Err(e) => {
             warn!("Failed to get bonding curve: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(ApiError::Internal(e.to_string()))
         }
     }
 }
 
-async fn vanity_stats_handler(
+async fn get_curve_handler(
     State(state): State<AppState>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+    axum::extract::Path(mint): axum::extract::Path<String>,
+) -> Result<Json<CurveResponse>, StatusCode> {
+    do_get_curve(&state, mint)
+        .await
+        .map(Json)
+        .map_err(|e| e.status_code())
+}
+
+// Streams bonding-curve updates for `mint` over a WebSocket. Multiple
+// subscribers on the same mint share one upstream poller; the poller tears
+// itself down once the last subscriber disconnects.
+async fn subscribe_curve_handler(
+    State(state): State<AppState>,
+    Path(mint): Path<String>,
+    ws: WebSocketUpgrade,
+) -> Result<axum::response::Response, StatusCode> {
+    // Validate the mint up front so bad requests fail before the upgrade.
+    mint.parse::<Pubkey>().map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let rx = subscribe_to_curve(&state, mint.clone()).await;
+    Ok(ws.on_upgrade(move |socket| handle_curve_socket(socket, rx)))
+}
+
+// Returns a receiver for `mint`'s curve-update channel, creating the
+// upstream polling task on first subscription.
+async fn subscribe_to_curve(state: &AppState, mint: String) -> broadcast::Receiver<String> {
+    let mut subs = state.curve_subscriptions.write().await;
+    if let Some(tx) = subs.get(&mint) {
+        return tx.subscribe();
+    }
+
+    let (tx, rx) = broadcast::channel(32);
+    subs.insert(mint.clone(), tx.clone());
+    drop(subs);
+
+    let state = state.clone();
+    tokio::spawn(async move {
+        poll_curve_upstream(state, mint, tx).await;
+    });
+
+    rx
+}
+
+// Bounded polling loop standing in for a real `accountSubscribe` WebSocket
+// subscription on the bonding-curve PDA: polls on an interval, broadcasts
+// each update to every subscriber of this mint, and exits (removing the
+// shared upstream) once nobody is listening anymore.
+async fn poll_curve_upstream(state: AppState, mint: String, tx: broadcast::Sender<String>) {
+    let Ok(mint_pubkey) = mint.parse::<Pubkey>() else {
+        return;
+    };
+
+    info!("Starting bonding-curve poll loop for {}", mint);
+    loop {
+        if tx.receiver_count() == 0 {
+            // A subscriber may have shown up between the lock-free check
+            // above and us getting here, so re-check under the same write
+            // lock `subscribe_to_curve` inserts/subscribes under: if a new
+            // receiver snuck in, it did so while holding this lock, so a
+            // zero count observed with the lock held is final.
+            let mut subs = state.curve_subscriptions.write().await;
+            if tx.receiver_count() == 0 {
+                info!("No subscribers left for {}, tearing down poll loop", mint);
+                subs.remove(&mint);
+                break;
+            }
+            drop(subs);
+        }
+
+        match state.pump_client().await.get_bonding_curve_account(&mint_pubkey).await {
+            Ok(curve) => {
+                let curve_json = serde_json::to_value(&curve)
+                    .unwrap_or_else(|_| serde_json::json!({"error": "Failed to serialize curve"}));
+                let payload = serde_json::json!({
+                    "mint": mint,
+                    "curve": curve_json,
+                    "derived": derive_curve_metrics(&curve_json),
+                });
+                let _ = tx.send(payload.to_string());
+            }
+            Err(e) => {
+                warn!("Curve poll failed for {}: {}", mint, e);
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+}
+
+// Derives price/market-cap/graduation-progress from a serialized
+// `BondingCurveAccount`. Reads fields defensively since the exact shape
+// comes from the `pumpfun` crate's serde impl.
+fn derive_curve_metrics(curve_json: &serde_json::Value) -> serde_json::Value {
+    let virtual_sol_reserves = curve_json["virtual_sol_reserves"].as_u64().unwrap_or(0);
+    let virtual_token_reserves = curve_json["virtual_token_reserves"].as_u64().unwrap_or(0);
+    let real_sol_reserves = curve_json["real_sol_reserves"].as_u64().unwrap_or(0);
+    let token_total_supply = curve_json["token_total_supply"].as_u64().unwrap_or(0);
+
+    let price_sol_per_token = if virtual_token_reserves > 0 {
+        virtual_sol_reserves as f64 / virtual_token_reserves as f64
+    } else {
+        0.0
+    };
+    let market_cap_sol = price_sol_per_token * token_total_supply as f64 / LAMPORTS_PER_SOL as f64;
+    let progress_pct = ((real_sol_reserves as f64 / GRADUATION_LAMPORTS as f64) * 100.0).min(100.0);
+
+    serde_json::json!({
+        "price_sol_per_token": price_sol_per_token,
+        "market_cap_sol": market_cap_sol,
+        "progress_pct": progress_pct,
+    })
+}
+
+async fn handle_curve_socket(mut socket: WebSocket, mut rx: broadcast::Receiver<String>) {
+    loop {
+        tokio::select! {
+            update = rx.recv() => {
+                match update {
+                    Ok(payload) => {
+                        if socket.send(Message::Text(payload)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+            incoming = socket.recv() => {
+                match incoming {
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Err(_)) => break,
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+async fn do_vanity_stats(state: &AppState) -> serde_json::Value {
     let pool_size = state.vanity_service.pool_size().await;
-    
-    Ok(Json(serde_json::json!({
+
+    serde_json::json!({
         "pool_size": pool_size,
         "suffix": "pump",
         "authority_pubkey": state.vanity_service.get_authority_keypair().pubkey().to_string(),
         "method": "create_with_seed_fast"
-    })))
+    })
+}
+
+async fn vanity_stats_handler(
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    Ok(Json(do_vanity_stats(&state).await))
+}
+
+async fn metrics_handler(State(state): State<AppState>) -> String {
+    use std::fmt::Write;
+
+    let mut out = state.metrics.render().await;
+
+    let pool_size = state.vanity_service.pool_size().await;
+    let _ = writeln!(out, "# TYPE vanity_pool_size gauge");
+    let _ = writeln!(out, "vanity_pool_size {}", pool_size);
+
+    let _ = writeln!(out, "# TYPE vanity_addresses_generated_total counter");
+    let _ = writeln!(
+        out,
+        "vanity_addresses_generated_total {}",
+        state.vanity_service.generated_total()
+    );
+
+    out
+}
+
+// JSON-RPC 2.0 request/response plumbing, mirroring Solana's own method-
+// dispatch + batching model so clients that already speak JSON-RPC can
+// integrate without a REST shim. Dispatches to the same `do_*` logic the
+// REST handlers above use.
+
+#[derive(Deserialize)]
+struct JsonRpcRequest {
+    #[serde(default)]
+    #[allow(dead_code)]
+    jsonrpc: Option<String>,
+    method: String,
+    #[serde(default)]
+    params: serde_json::Value,
+    #[serde(default)]
+    id: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonRpcPayload {
+    Batch(Vec<JsonRpcRequest>),
+    Single(JsonRpcRequest),
+}
+
+#[derive(Serialize)]
+struct JsonRpcErrorBody {
+    code: i64,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct JsonRpcResponse {
+    jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<JsonRpcErrorBody>,
+    id: Option<serde_json::Value>,
+}
+
+impl From<ApiError> for JsonRpcErrorBody {
+    fn from(err: ApiError) -> Self {
+        match err {
+            ApiError::BadRequest(message) => JsonRpcErrorBody { code: -32602, message },
+            ApiError::Internal(message) => JsonRpcErrorBody { code: -32000, message },
+            ApiError::MethodNotFound(method) => JsonRpcErrorBody {
+                code: -32601,
+                message: format!("method not found: {}", method),
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GetCurveParams {
+    mint: String,
+}
+
+fn parse_rpc_params<T: serde::de::DeserializeOwned>(params: &serde_json::Value) -> Result<T, ApiError> {
+    serde_json::from_value(params.clone())
+        .map_err(|e| ApiError::BadRequest(format!("invalid params: {}", e)))
+}
+
+async fn dispatch_rpc(state: &AppState, request: JsonRpcRequest) -> JsonRpcResponse {
+    let id = request.id.clone();
+
+    let outcome: Result<serde_json::Value, ApiError> = match request.method.as_str() {
+        "createToken" => match parse_rpc_params(&request.params) {
+            Ok(req) => do_create_token(state, req)
+                .await
+                .map(|r| serde_json::to_value(r).unwrap_or(serde_json::Value::Null)),
+            Err(e) => Err(e),
+        },
+        "createAndBuy" => match parse_rpc_params(&request.params) {
+            Ok(req) => do_create_and_buy(state, req)
+                .await
+                .map(|r| serde_json::to_value(r).unwrap_or(serde_json::Value::Null)),
+            Err(e) => Err(e),
+        },
+        "buy" => match parse_rpc_params(&request.params) {
+            Ok(req) => do_buy(state, req)
+                .await
+                .map(|r| serde_json::to_value(r).unwrap_or(serde_json::Value::Null)),
+            Err(e) => Err(e),
+        },
+        "sell" => match parse_rpc_params(&request.params) {
+            Ok(req) => do_sell(state, req)
+                .await
+                .map(|r| serde_json::to_value(r).unwrap_or(serde_json::Value::Null)),
+            Err(e) => Err(e),
+        },
+        "getCurve" => match parse_rpc_params::<GetCurveParams>(&request.params) {
+            Ok(params) => do_get_curve(state, params.mint)
+                .await
+                .map(|r| serde_json::to_value(r).unwrap_or(serde_json::Value::Null)),
+            Err(e) => Err(e),
+        },
+        "vanityStats" => Ok(do_vanity_stats(state).await),
+        "getHealth" => Ok(serde_json::to_value(do_health(state).await).unwrap_or(serde_json::Value::Null)),
+        other => Err(ApiError::MethodNotFound(other.to_string())),
+    };
+
+    match outcome {
+        Ok(result) => JsonRpcResponse {
+            jsonrpc: "2.0",
+            result: Some(result),
+            error: None,
+            id,
+        },
+        Err(e) => JsonRpcResponse {
+            jsonrpc: "2.0",
+            result: None,
+            error: Some(e.into()),
+            id,
+        },
+    }
+}
+
+// Upper bound on how many calls a single JSON-RPC batch may pack into one
+// HTTP request, so a batch can't be used to bypass the per-HTTP-request
+// charge the auth middleware already applies to the caller's rate-limit
+// bucket (see the per-call `try_acquire` below for the rest of that fix).
+const MAX_RPC_BATCH_SIZE: usize = 20;
+
+async fn rpc_handler(
+    State(state): State<AppState>,
+    caller: Option<Extension<Pubkey>>,
+    Json(raw): Json<serde_json::Value>,
+) -> Json<serde_json::Value> {
+    let payload: JsonRpcPayload = match serde_json::from_value(raw) {
+        Ok(payload) => payload,
+        Err(e) => {
+            let response = JsonRpcResponse {
+                jsonrpc: "2.0",
+                result: None,
+                error: Some(JsonRpcErrorBody {
+                    code: -32600,
+                    message: format!("Invalid Request: {}", e),
+                }),
+                id: None,
+            };
+            return Json(serde_json::to_value(response).unwrap_or(serde_json::Value::Null));
+        }
+    };
+
+    match payload {
+        JsonRpcPayload::Single(request) => {
+            let response = dispatch_rpc(&state, request).await;
+            Json(serde_json::to_value(response).unwrap_or(serde_json::Value::Null))
+        }
+        JsonRpcPayload::Batch(requests) => {
+            if requests.len() > MAX_RPC_BATCH_SIZE {
+                let response = JsonRpcResponse {
+                    jsonrpc: "2.0",
+                    result: None,
+                    error: Some(JsonRpcErrorBody {
+                        code: -32600,
+                        message: format!("batch of {} exceeds max size {}", requests.len(), MAX_RPC_BATCH_SIZE),
+                    }),
+                    id: None,
+                };
+                return Json(serde_json::to_value(response).unwrap_or(serde_json::Value::Null));
+            }
+
+            let mut responses = Vec::with_capacity(requests.len());
+            // The auth middleware already charged one token for this HTTP
+            // request; charge one more per additional call so a batch can't
+            // be used to run N calls for the price of one.
+            for (i, request) in requests.into_iter().enumerate() {
+                if i > 0 {
+                    if let Some(Extension(caller)) = caller {
+                        if !state.rate_limiter.try_acquire(caller).await {
+                            let id = request.id.clone();
+                            responses.push(JsonRpcResponse {
+                                jsonrpc: "2.0",
+                                result: None,
+                                error: Some(JsonRpcErrorBody {
+                                    code: -32000,
+                                    message: "rate limit exceeded".to_string(),
+                                }),
+                                id,
+                            });
+                            continue;
+                        }
+                    }
+                }
+                responses.push(dispatch_rpc(&state, request).await);
+            }
+            Json(serde_json::to_value(responses).unwrap_or(serde_json::Value::Null))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Builds the `X-Caller-*` header set a real client would send: sign
+    // `nonce || sha256(body)` with `signer`, mirroring `authenticate_caller`.
+    fn signed_headers(signer: &Keypair, nonce_millis: u64, body: &[u8]) -> HeaderMap {
+        let nonce_str = nonce_millis.to_string();
+        let body_hash = solana_sdk::hash::hash(body);
+        let mut message = Vec::with_capacity(nonce_str.len() + body_hash.as_ref().len());
+        message.extend_from_slice(nonce_str.as_bytes());
+        message.extend_from_slice(body_hash.as_ref());
+        let signature = signer.sign_message(&message);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-caller-pubkey", signer.pubkey().to_string().parse().unwrap());
+        headers.insert("x-caller-nonce", nonce_str.parse().unwrap());
+        headers.insert("x-caller-signature", signature.to_string().parse().unwrap());
+        headers
+    }
+
+    fn now_millis() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64
+    }
+
+    #[tokio::test]
+    async fn authenticate_caller_accepts_valid_signed_request() {
+        let signer = Keypair::new();
+        let mut allowlist = HashSet::new();
+        allowlist.insert(signer.pubkey());
+        let nonce_cache = NonceCache::new();
+        let body = br#"{"mint":"abc"}"#;
+        let headers = signed_headers(&signer, now_millis(), body);
+
+        let caller = authenticate_caller(&allowlist, &nonce_cache, &headers, body)
+            .await
+            .expect("valid signed request should authenticate");
+        assert_eq!(caller, signer.pubkey());
+    }
+
+    #[tokio::test]
+    async fn authenticate_caller_rejects_pubkey_not_in_allowlist() {
+        let signer = Keypair::new();
+        let allowlist = HashSet::new(); // signer deliberately not added
+        let nonce_cache = NonceCache::new();
+        let body = b"{}";
+        let headers = signed_headers(&signer, now_millis(), body);
+
+        let err = authenticate_caller(&allowlist, &nonce_cache, &headers, body)
+            .await
+            .expect_err("caller outside the allowlist must be rejected");
+        assert_eq!(err, "caller pubkey not in allowlist");
+    }
+
+    #[tokio::test]
+    async fn authenticate_caller_rejects_tampered_body() {
+        let signer = Keypair::new();
+        let mut allowlist = HashSet::new();
+        allowlist.insert(signer.pubkey());
+        let nonce_cache = NonceCache::new();
+        let headers = signed_headers(&signer, now_millis(), br#"{"amount_sol":1}"#);
+
+        // Signature was computed over a different body than the one passed
+        // in here, so verification must fail.
+        let err = authenticate_caller(&allowlist, &nonce_cache, &headers, br#"{"amount_sol":1000}"#)
+            .await
+            .expect_err("signature over a different body must fail verification");
+        assert_eq!(err, "signature verification failed");
+    }
+
+    #[tokio::test]
+    async fn authenticate_caller_rejects_replayed_nonce() {
+        let signer = Keypair::new();
+        let mut allowlist = HashSet::new();
+        allowlist.insert(signer.pubkey());
+        let nonce_cache = NonceCache::new();
+        let body = b"{}";
+        let headers = signed_headers(&signer, now_millis(), body);
+
+        authenticate_caller(&allowlist, &nonce_cache, &headers, body)
+            .await
+            .expect("first use of a nonce must succeed");
+
+        let err = authenticate_caller(&allowlist, &nonce_cache, &headers, body)
+            .await
+            .expect_err("replaying the exact same (pubkey, nonce) pair must be rejected");
+        assert_eq!(err, "nonce already used");
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_exhausts_burst_then_refills() {
+        let limiter = RateLimiter::new(1000.0, 2.0); // fast refill keeps the test quick
+        let caller = Keypair::new().pubkey();
+
+        assert!(limiter.try_acquire(caller).await);
+        assert!(limiter.try_acquire(caller).await);
+        assert!(
+            !limiter.try_acquire(caller).await,
+            "burst of 2 should be exhausted on the 3rd call"
+        );
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(
+            limiter.try_acquire(caller).await,
+            "bucket should have refilled after waiting"
+        );
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_tracks_callers_independently() {
+        let limiter = RateLimiter::new(1.0, 1.0);
+        let caller_a = Keypair::new().pubkey();
+        let caller_b = Keypair::new().pubkey();
+
+        assert!(limiter.try_acquire(caller_a).await);
+        assert!(!limiter.try_acquire(caller_a).await);
+        assert!(
+            limiter.try_acquire(caller_b).await,
+            "a different caller must have its own bucket"
+        );
+    }
+
+    #[tokio::test]
+    async fn nonce_cache_allows_distinct_nonces_and_rejects_repeats() {
+        let cache = NonceCache::new();
+        let caller = Keypair::new().pubkey();
+
+        assert!(cache.check_and_record(caller, 1).await);
+        assert!(
+            cache.check_and_record(caller, 2).await,
+            "a different nonce from the same caller is fine"
+        );
+        assert!(
+            !cache.check_and_record(caller, 1).await,
+            "repeating a nonce must be rejected"
+        );
+    }
 }